@@ -0,0 +1,17 @@
+use helix_view::Editor;
+
+use crate::compositor;
+use crate::handlers::session;
+
+/// Called once the compositor and initial documents are in place; restores
+/// the working-directory's saved session when `auto_restore_session` is on.
+pub fn on_startup(cx: &mut compositor::Context) {
+    session::maybe_restore_on_startup(cx);
+}
+
+/// Called on the way out, before the editor is torn down; saves the
+/// working-directory's session when `auto_restore_session` is on, so it's
+/// there to restore via [`on_startup`] next time.
+pub fn on_shutdown(editor: &mut Editor) {
+    session::save_on_exit(editor);
+}
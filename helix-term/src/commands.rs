@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use helix_view::Editor;
+
+use crate::compositor;
+use crate::handlers::{position_history, session, wrap_when_typing};
+use crate::ui::PromptEvent;
+
+/// A compositor action queued by a static command. Static commands only ever
+/// see `&mut Editor` (the compositor itself is borrowed mutably by whatever
+/// is dispatching the command), so layers are pushed by queuing a callback
+/// here for the dispatcher to apply once the command returns and it can
+/// borrow the compositor again.
+pub type Callback = Box<dyn FnOnce(&mut compositor::Compositor)>;
+
+/// Context threaded through static (keymap-bound, no `:` prefix) commands.
+pub struct Context<'a> {
+    pub editor: &'a mut Editor,
+    /// Compositor actions queued by this command; drained and applied by the
+    /// dispatcher after the command returns.
+    pub callbacks: Vec<Callback>,
+}
+
+impl<'a> Context<'a> {
+    /// Queue a UI layer (picker, prompt, ...) to be pushed onto the
+    /// compositor once the command returns.
+    pub fn push_layer(&mut self, layer: Box<dyn compositor::Component>) {
+        self.callbacks
+            .push(Box::new(move |compositor: &mut compositor::Compositor| compositor.push(layer)));
+    }
+}
+
+pub type StaticCommandFn = fn(&mut Context);
+
+pub struct StaticCommand {
+    pub name: &'static str,
+    /// Default keymap binding, as a sequence of key names (e.g. `["g", "q"]`).
+    pub default_keys: &'static [&'static str],
+    pub fun: StaticCommandFn,
+}
+
+/// Keymap-bound commands with no `:` prefix, and their default bindings.
+pub const STATIC_COMMAND_LIST: &[StaticCommand] = &[
+    StaticCommand {
+        name: "reflow_selection",
+        default_keys: &["g", "q"],
+        fun: wrap_when_typing::reflow_selection,
+    },
+    StaticCommand {
+        name: "position_history_picker",
+        default_keys: &["space", "j"],
+        fun: position_history::position_history_picker,
+    },
+];
+
+pub type TypableCommandFn =
+    fn(&mut compositor::Context, &[Cow<str>], PromptEvent) -> anyhow::Result<()>;
+
+pub struct TypableCommand {
+    pub name: &'static str,
+    pub fun: TypableCommandFn,
+}
+
+/// `:`-prefixed commands, looked up by name from the command-mode prompt.
+pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
+    TypableCommand { name: "session-save", fun: session::session_save },
+    TypableCommand { name: "session-load", fun: session::session_load },
+];
@@ -0,0 +1,9 @@
+pub(crate) mod position_history;
+pub(crate) mod session;
+pub(crate) mod wrap_when_typing;
+
+use helix_view::handlers::Handlers;
+
+pub(crate) fn register_hooks(handlers: &Handlers) {
+    wrap_when_typing::register_hooks(handlers);
+}
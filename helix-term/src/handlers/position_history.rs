@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use helix_core::Selection;
+use helix_view::file_info::HistoryEntry;
+
+use crate::commands::Context;
+use crate::ui::{self, overlaid};
+
+/// Open a picker over the cross-session position history recorded by
+/// `FileInfoDb` (path, line and a snippet of the line text, newest first),
+/// so the user can jump back to files and spots they were editing in this
+/// session or an earlier one.
+pub fn position_history_picker(cx: &mut Context) {
+    let entries = cx.editor.file_info_db.recent_history(200);
+
+    let picker = ui::Picker::new(entries, (), |cx, entry: &HistoryEntry, action| {
+        let path = PathBuf::from(&entry.filepath);
+        if let Err(err) = cx.editor.open(&path, action) {
+            cx.editor.set_error(format!("Unable to open \"{}\": {}", entry.filepath, err));
+            return;
+        }
+
+        let (view, doc) = current!(cx.editor);
+        let text = doc.text();
+        let line = entry.line.min(text.len_lines().saturating_sub(1));
+        let column = entry.column.min(text.line(line).len_chars());
+        let pos = text.line_to_char(line) + column;
+        doc.set_selection(view.id, Selection::point(pos));
+    })
+    .with_preview(|_editor, entry| Some((entry.filepath.clone().into(), Some((entry.line, entry.line)))));
+
+    cx.push_layer(Box::new(overlaid(picker)));
+}
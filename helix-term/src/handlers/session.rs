@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use helix_core::Selection;
+use helix_view::editor::Action;
+use helix_view::file_info::{FileInfoDb, SessionSnapshot};
+use helix_view::Editor;
+
+use crate::compositor;
+use crate::ui::PromptEvent;
+
+/// The file path of the document shown in each of `editor`'s views (one
+/// entry per view; a document open in no view is left out), with the
+/// focused one moved to the front. This is what `:session-save` persists —
+/// see [`SessionSnapshot`] for why split direction and sizing aren't
+/// captured alongside it; `Tree` doesn't expose its container topology
+/// publicly, only the flat `views()` list and which view is focused, so
+/// that focus is the one piece of "layout" this can faithfully round-trip:
+/// `restore_session` reopens the first path with `Action::Replace`, so
+/// putting the focused document there means it's focused again afterwards.
+fn open_document_paths(editor: &Editor) -> Vec<String> {
+    let mut focused_first = Vec::new();
+    let mut rest = Vec::new();
+    for (view, focused) in editor.tree.views() {
+        let Some(doc) = editor.documents.get(&view.doc) else {
+            continue;
+        };
+        let Some(path) = doc.path() else {
+            continue;
+        };
+        let path = path.to_string_lossy().into_owned();
+        if focused {
+            focused_first.push(path);
+        } else {
+            rest.push(path);
+        }
+    }
+    focused_first.extend(rest);
+    focused_first
+}
+
+/// Reopen every document recorded in `snapshot`, one split per document,
+/// restoring each one's selection and scroll offset via the existing
+/// `load_position` path.
+pub fn restore_session(cx: &mut compositor::Context, snapshot: &SessionSnapshot) {
+    let mut first_view = None;
+    for (idx, filepath) in snapshot.documents.iter().enumerate() {
+        let path = PathBuf::from(filepath);
+        let action = if idx == 0 { Action::Replace } else { Action::HorizontalSplit };
+
+        if let Err(err) = cx.editor.open(&path, action) {
+            cx.editor.set_error(format!("Unable to reopen \"{}\": {}", filepath, err));
+            continue;
+        }
+        // Each split takes focus as it opens; remember the first view so
+        // focus can be handed back to it once every document is open.
+        first_view.get_or_insert(cx.editor.tree.focus);
+
+        if let Some(position) = cx.editor.file_info_db.load_position(&path) {
+            let (view, doc) = current!(cx.editor);
+            let text = doc.text();
+            let max_char = text.len_chars();
+            let selection = if position.selection.ranges().iter().any(|range| range.to() > max_char) {
+                let cursor = position.selection.primary().cursor(text.slice(..)).min(max_char);
+                Selection::point(cursor)
+            } else {
+                position.selection
+            };
+            doc.set_selection(view.id, selection);
+            view.offset.vertical_offset = position.scroll_offset;
+        }
+    }
+
+    if let Some(view_id) = first_view {
+        cx.editor.focus(view_id);
+    }
+}
+
+fn session_name(args: &[Cow<str>]) -> String {
+    args.first()
+        .map(|arg| arg.to_string())
+        .unwrap_or_else(|| FileInfoDb::session_name_for_cwd(&helix_stdx::env::current_working_dir()))
+}
+
+/// `:session-save [name]` — save the set of open documents, defaulting to a
+/// name derived from the current working directory.
+pub fn session_save(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    _event: PromptEvent,
+) -> anyhow::Result<()> {
+    let name = session_name(args);
+    let snapshot = SessionSnapshot {
+        working_dir: helix_stdx::env::current_working_dir().to_string_lossy().into_owned(),
+        documents: open_document_paths(cx.editor),
+    };
+
+    cx.editor.file_info_db.save_session(&name, &snapshot)?;
+    cx.editor.set_status(format!("Saved session '{}'", name));
+
+    Ok(())
+}
+
+/// `:session-load [name]` — reopen every file from a session previously
+/// written by `:session-save`.
+pub fn session_load(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    _event: PromptEvent,
+) -> anyhow::Result<()> {
+    let name = session_name(args);
+
+    let Some(snapshot) = cx.editor.file_info_db.load_session(&name) else {
+        cx.editor.set_error(format!("No saved session named '{}'", name));
+        return Ok(());
+    };
+
+    restore_session(cx, &snapshot);
+
+    Ok(())
+}
+
+/// Called once at startup; restores the session for the current working
+/// directory when `auto_restore_session` is enabled in the config.
+pub fn maybe_restore_on_startup(cx: &mut compositor::Context) {
+    if !cx.editor.config().auto_restore_session {
+        return;
+    }
+
+    let name = FileInfoDb::session_name_for_cwd(&helix_stdx::env::current_working_dir());
+    if let Some(snapshot) = cx.editor.file_info_db.load_session(&name) {
+        restore_session(cx, &snapshot);
+    }
+}
+
+/// Called once at shutdown, mirroring `maybe_restore_on_startup`'s naming so
+/// the saved session is picked back up automatically on the next launch.
+pub fn save_on_exit(editor: &mut Editor) {
+    if !editor.config().auto_restore_session {
+        return;
+    }
+
+    let name = FileInfoDb::session_name_for_cwd(&helix_stdx::env::current_working_dir());
+    let snapshot = SessionSnapshot {
+        working_dir: helix_stdx::env::current_working_dir().to_string_lossy().into_owned(),
+        documents: open_document_paths(editor),
+    };
+
+    if let Err(err) = editor.file_info_db.save_session(&name, &snapshot) {
+        log::debug!("failed to save session '{}' on exit: {}", name, err);
+    }
+}
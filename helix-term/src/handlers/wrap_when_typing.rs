@@ -1,10 +1,296 @@
-use helix_core::{Selection, Transaction, SmartString, indent};
+use helix_core::{RopeSlice, Selection, Transaction, SmartString, indent};
 use helix_event::register_hook;
 use helix_view::handlers::Handlers;
 use helix_core::chars::char_is_whitespace;
+use unicode_width::UnicodeWidthChar;
 
+use crate::commands::Context;
 use crate::events::PostInsertChar;
 
+/// Visual width of `ch` at `col`, expanding tabs to the next `tab_width`
+/// stop the same way Helix's own visual positioning does in `position.rs`.
+fn visual_char_width(ch: char, col: usize, tab_width: usize) -> usize {
+    if ch == '\t' {
+        tab_width - (col % tab_width)
+    } else {
+        UnicodeWidthChar::width(ch).unwrap_or(0)
+    }
+}
+
+/// If `break_pos` sits inside a comment, return the leader (with trailing
+/// space) that should be prepended to the wrapped continuation line so it
+/// keeps reading as a comment instead of trailing off into bare prose.
+fn comment_leader_at(doc: &helix_view::Document, break_pos: usize) -> Option<String> {
+    let syntax = doc.syntax()?;
+    let language_config = doc.language_config()?;
+    let text = doc.text();
+    let byte_pos = text.char_to_byte(break_pos);
+
+    let mut node = syntax
+        .tree()
+        .root_node()
+        .descendant_for_byte_range(byte_pos, byte_pos)?;
+    loop {
+        if node.kind().contains("comment") {
+            break;
+        }
+        node = node.parent()?;
+    }
+
+    let comment_text = text.slice(..).byte_slice(node.start_byte()..node.end_byte()).to_string();
+    let comment_text = comment_text.trim_start();
+
+    if let Some(block_tokens) = &language_config.block_comment_tokens {
+        if block_tokens
+            .iter()
+            .any(|token| comment_text.starts_with(token.start.as_str()))
+        {
+            return Some("* ".to_string());
+        }
+    }
+
+    if let Some(line_tokens) = &language_config.comment_tokens {
+        if let Some(token) = line_tokens
+            .iter()
+            .find(|token| comment_text.starts_with(token.as_str()))
+        {
+            return Some(format!("{} ", token));
+        }
+    }
+
+    None
+}
+
+/// Reflow the paragraph(s) touched by the current selection to `doc.text_width()`.
+///
+/// Unlike the typing-wrap hook below, which only ever breaks the single line
+/// the cursor sits on, this gathers each contiguous paragraph the selection
+/// touches, joins it into a word stream and re-breaks it with the
+/// minimum-raggedness dynamic program: rather than greedily packing every
+/// line but the last, it minimizes the summed squared slack across all
+/// lines, so the ragged edge is spread evenly instead of piling up on one
+/// short line.
+pub fn reflow_selection(cx: &mut Context) {
+    let config = cx.editor.config();
+    let indent_heuristic = config.indent_heuristic.clone();
+    let loader = cx.editor.syn_loader.load();
+
+    let (view, doc) = current!(cx.editor);
+    let text_width = doc.text_width();
+    if text_width == 0 {
+        return;
+    }
+
+    let mut changes = Vec::new();
+    {
+        let text = doc.text();
+        let selection = doc.selection(view.id);
+
+        // Multiple cursors can land in the same paragraph, or in paragraphs
+        // that extend into each other's blank-line boundaries; gather every
+        // span first and merge overlaps so `Transaction::change` only ever
+        // sees sorted, non-overlapping ranges.
+        let mut spans = Vec::new();
+        for range in selection.ranges() {
+            spans.extend(paragraphs_in_range(text.slice(..), range.from(), range.to()));
+        }
+
+        for (start, end) in merge_spans(spans) {
+            // Leave the paragraph's own leading indent on its first line
+            // untouched rather than regenerating it, and only rewrap the
+            // content after it.
+            let content_start = skip_leading_whitespace(text.slice(..), start);
+            let line_idx = text.char_to_line(content_start);
+            let indent_str = indent::indent_for_newline(
+                &loader,
+                doc.syntax(),
+                &indent_heuristic,
+                &doc.indent_style,
+                doc.tab_width(),
+                text.slice(..),
+                line_idx,
+                content_start,
+                line_idx,
+            );
+
+            // Continuation lines are prefixed with `indent_str`, so budget
+            // for its width or the wrapped text itself overflows text_width.
+            let tab_width = doc.tab_width();
+            let indent_width = indent_str
+                .chars()
+                .fold(0, |col, ch| col + visual_char_width(ch, col, tab_width));
+            let line_budget = text_width.saturating_sub(indent_width).max(1);
+
+            let new_text = reflow_paragraph(text.slice(content_start..end), line_budget, &indent_str);
+            changes.push((content_start, end, Some(SmartString::from(new_text.as_str()))));
+        }
+    }
+
+    if changes.is_empty() {
+        return;
+    }
+
+    let transaction = Transaction::change(doc.text(), changes.into_iter());
+    doc.apply(&transaction, view.id);
+}
+
+/// Walk outward from `[sel_start, sel_end)` to the enclosing blank-line
+/// boundaries on either side, then split the resulting span on blank lines
+/// so each contiguous non-blank run is reflowed as its own paragraph.
+fn paragraphs_in_range(text: RopeSlice, sel_start: usize, sel_end: usize) -> Vec<(usize, usize)> {
+    let is_blank_line = |line_idx: usize| text.line(line_idx).chars().all(char_is_whitespace);
+    let total_lines = text.len_lines();
+
+    let start_line = text.char_to_line(sel_start);
+    let end_pos = if sel_end > sel_start { sel_end - 1 } else { sel_start };
+    let end_line = text.char_to_line(end_pos);
+
+    let mut first_line = start_line;
+    while first_line > 0 && !is_blank_line(first_line - 1) {
+        first_line -= 1;
+    }
+    let mut last_line = end_line;
+    while last_line + 1 < total_lines && !is_blank_line(last_line + 1) {
+        last_line += 1;
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut line = first_line;
+    while line <= last_line {
+        if is_blank_line(line) {
+            line += 1;
+            continue;
+        }
+        let para_start_line = line;
+        while line <= last_line && !is_blank_line(line) {
+            line += 1;
+        }
+        let para_end_line = line - 1;
+
+        paragraphs.push((text.line_to_char(para_start_line), line_end_char(text, para_end_line)));
+    }
+
+    paragraphs
+}
+
+/// Char offset of the first non-whitespace character at or after `start`,
+/// stopping at the first newline (i.e. only skips indent on `start`'s own
+/// line, not blank lines or further indentation on later lines).
+fn skip_leading_whitespace(text: RopeSlice, start: usize) -> usize {
+    let mut pos = start;
+    let len = text.len_chars();
+    while pos < len {
+        let ch = text.char(pos);
+        if ch == '\n' || !char_is_whitespace(ch) {
+            break;
+        }
+        pos += 1;
+    }
+    pos
+}
+
+/// Sort `spans` and fold together any that overlap or touch, so callers get
+/// back a set of disjoint, non-adjacent `(start, end)` ranges.
+fn merge_spans(mut spans: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    spans.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        if let Some(last) = merged.last_mut() {
+            if start <= last.1 {
+                last.1 = last.1.max(end);
+                continue;
+            }
+        }
+        merged.push((start, end));
+    }
+    merged
+}
+
+/// Char offset of the end of `line_idx`, excluding its trailing newline.
+fn line_end_char(text: RopeSlice, line_idx: usize) -> usize {
+    let mut end = if line_idx + 1 < text.len_lines() {
+        text.line_to_char(line_idx + 1)
+    } else {
+        text.len_chars()
+    };
+    if end > text.line_to_char(line_idx) && text.char(end - 1) == '\n' {
+        end -= 1;
+    }
+    end
+}
+
+/// Re-break `paragraph` into lines of at most `text_width` columns using the
+/// minimum-raggedness dynamic program described in `reflow_selection`'s
+/// doc-comment: `best[i]` is the minimal cost to lay out words `i..n`,
+/// `cost(i, j)` is the squared slack of putting words `i..j` on one line
+/// (infinite if they don't fit), and the final line always costs `0`
+/// regardless of its slack. A single word wider than `text_width` is given
+/// cost `0` on its own line so the DP always has a feasible choice.
+fn reflow_paragraph(paragraph: RopeSlice, text_width: usize, indent_str: &str) -> String {
+    let paragraph_str = paragraph.to_string();
+    let words: Vec<&str> = paragraph_str.split_whitespace().collect();
+    if words.is_empty() {
+        return paragraph_str;
+    }
+
+    let n = words.len();
+    let mut prefix = vec![0i64; n + 1];
+    for i in 0..n {
+        prefix[i + 1] = prefix[i] + words[i].chars().count() as i64;
+    }
+    let width_between = |i: usize, j: usize| prefix[j] - prefix[i] + (j - i - 1) as i64;
+
+    let cost = |i: usize, j: usize| -> f64 {
+        let width = width_between(i, j);
+        if width <= text_width as i64 {
+            // The last line never contributes slack cost, however ragged.
+            if j == n {
+                0.0
+            } else {
+                let slack = (text_width as i64 - width) as f64;
+                slack * slack
+            }
+        } else if j == i + 1 {
+            // A single word longer than text_width still has to go somewhere.
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    };
+
+    let mut best = vec![f64::INFINITY; n + 1];
+    let mut break_at = vec![n; n + 1];
+    best[n] = 0.0;
+    for i in (0..n).rev() {
+        for j in (i + 1)..=n {
+            let candidate = cost(i, j) + best[j];
+            if candidate < best[i] {
+                best[i] = candidate;
+                break_at[i] = j;
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let j = break_at[i];
+        lines.push(words[i..j].join(" "));
+        i = j;
+    }
+
+    let mut out = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+            out.push_str(indent_str);
+        }
+        out.push_str(line);
+    }
+    out
+}
+
 pub(super) fn register_hooks(_handlers: &Handlers) {
     register_hook!(move |event: &mut PostInsertChar<'_, '_>| {
         let config = event.cx.editor.config();
@@ -40,18 +326,28 @@ pub(super) fn register_hooks(_handlers: &Handlers) {
 
                 let line = text.slice(line_start..line_end);
                 let line_str = line.to_string();
+                let tab_width = doc.tab_width();
+
+                // Compute the line's total visual width, expanding tabs and
+                // accounting for double-width graphemes, rather than just
+                // counting chars (which wraps CJK/emoji too late and
+                // tab-indented lines too early).
+                let mut line_visual_width = 0usize;
+                for ch in line_str.chars() {
+                    line_visual_width += visual_char_width(ch, line_visual_width, tab_width);
+                }
 
                 // Check if line exceeds text_width
-                if line_str.chars().count() > text_width && text_width > 0 {
+                if line_visual_width > text_width && text_width > 0 {
                     // Find the last whitespace before or at text_width
                     let mut last_space_before_width = None;
                     let mut first_space_after_width = None;
-                    let mut char_count = 0;
+                    let mut visual_col = 0usize;
 
                     for (idx, ch) in line_str.char_indices() {
-                        char_count += 1;
+                        visual_col += visual_char_width(ch, visual_col, tab_width);
                         if char_is_whitespace(ch) {
-                            if char_count <= text_width {
+                            if visual_col <= text_width {
                                 last_space_before_width = Some(idx);
                             } else if first_space_after_width.is_none() {
                                 // Found first space after text_width
@@ -112,6 +408,9 @@ pub(super) fn register_hooks(_handlers: &Handlers) {
             // Create the new text with newline + indentation
             let mut new_text = String::from("\n");
             new_text.push_str(&indent_str);
+            if let Some(leader) = comment_leader_at(doc, break_pos) {
+                new_text.push_str(&leader);
+            }
 
             let transaction = Transaction::change_by_selection(text, &Selection::single(break_pos, next_char_pos), |range| {
                 (range.from(), range.to(), Some(SmartString::from(new_text.as_str())))
@@ -121,4 +420,50 @@ pub(super) fn register_hooks(_handlers: &Handlers) {
 
         Ok(())
     });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::Rope;
+
+    fn reflow(paragraph: &str, text_width: usize, indent_str: &str) -> String {
+        let rope = Rope::from_str(paragraph);
+        reflow_paragraph(rope.slice(..), text_width, indent_str)
+    }
+
+    #[test]
+    fn reflow_wraps_at_width() {
+        let out = reflow("one two three four five", 11, "");
+        assert_eq!(out, "one two\nthree four\nfive");
+    }
+
+    #[test]
+    fn reflow_prefixes_continuation_lines_with_indent() {
+        let out = reflow("one two three four five", 11, "  ");
+        assert_eq!(out, "one two\n  three four\n  five");
+    }
+
+    #[test]
+    fn reflow_keeps_overlong_single_word_on_its_own_line() {
+        let out = reflow("short reallyreallyreallylongword ok", 10, "");
+        assert_eq!(out, "short\nreallyreallyreallylongword\nok");
+    }
+
+    #[test]
+    fn reflow_empty_paragraph_is_unchanged() {
+        assert_eq!(reflow("   ", 10, ""), "   ");
+    }
+
+    #[test]
+    fn merge_spans_folds_overlapping_and_touching_ranges() {
+        let merged = merge_spans(vec![(10, 20), (0, 5), (5, 12), (25, 30)]);
+        assert_eq!(merged, vec![(0, 20), (25, 30)]);
+    }
+
+    #[test]
+    fn merge_spans_leaves_disjoint_ranges_separate() {
+        let merged = merge_spans(vec![(0, 2), (5, 7)]);
+        assert_eq!(merged, vec![(0, 2), (5, 7)]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,56 @@
+use helix_core::indent::IndentationHeuristic;
+use serde::{Deserialize, Serialize};
+
+use crate::{DocumentId, Editor, ViewId};
+
+/// Runtime-configurable editor behavior. `#[serde(default)]` means an older
+/// config file missing a field (e.g. one predating `auto_restore_session`)
+/// just falls back to `Default::default()` for it instead of failing to
+/// parse.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub wrap_when_typing: bool,
+    pub indent_heuristic: IndentationHeuristic,
+    /// Save the session named after the current working directory on exit
+    /// and reopen it on startup. See `helix_term::handlers::session`.
+    pub auto_restore_session: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            wrap_when_typing: false,
+            indent_heuristic: IndentationHeuristic::default(),
+            auto_restore_session: false,
+        }
+    }
+}
+
+impl Editor {
+    /// Persist `doc_id`'s cursor position (as seen through `view_id`) to
+    /// `file_info_db` before the document goes away, so it can be restored
+    /// the next time this path is opened.
+    pub fn save_document_position(&mut self, doc_id: DocumentId, view_id: ViewId) {
+        let Some(doc) = self.documents.get(&doc_id) else {
+            return;
+        };
+        let Some(path) = doc.path() else {
+            return;
+        };
+        let path = path.to_path_buf();
+
+        let scroll_offset = self
+            .tree
+            .try_get(view_id)
+            .map(|view| view.offset.vertical_offset)
+            .unwrap_or(0);
+
+        if let Err(err) =
+            self.file_info_db
+                .save_position(&path, doc.selection(view_id), doc.text(), scroll_offset)
+        {
+            log::debug!("failed to save position for {:?}: {}", path, err);
+        }
+    }
+}
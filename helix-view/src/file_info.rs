@@ -1,25 +1,96 @@
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use rusqlite::{Connection, Result};
-use helix_core::Selection;
+use helix_core::{Range, Selection};
 use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+/// Default number of rows kept in the `position_history` ring buffer. See
+/// [`FileInfoDb::set_history_limit`] to override it.
+const DEFAULT_HISTORY_LIMIT: usize = 200;
+
+/// How much of a line's text to keep in a history snippet.
+const HISTORY_SNIPPET_MAX_CHARS: usize = 120;
 
 pub struct FileInfoDb {
     conn: Option<Connection>,
     enabled: bool,
+    history_limit: usize,
+}
+
+/// One entry in the cross-session edit-location jumplist, as shown in the
+/// "jump back to where I was" picker.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub filepath: String,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub timestamp: i64,
 }
 
 #[derive(Debug)]
 pub struct FilePosition {
     pub line: usize,
     pub column: usize,
+    /// The full selection (all ranges, plus which one was primary) as it was
+    /// when the file was last saved, so multi-cursor layouts and block
+    /// selections survive closing and reopening a file.
+    pub selection: Selection,
+    /// The view's vertical scroll offset, in lines.
+    pub scroll_offset: usize,
+}
+
+/// A saved editor session: the documents that were open, keyed by working
+/// directory (or a user-chosen name), with the focused one first. This is a
+/// deliberately scoped-down capture of "layout": `Tree` doesn't expose its
+/// container topology (split direction, relative sizes) outside its own
+/// module, only the flat list of views and which one is focused, so that's
+/// all `:session-save`/`:session-load` round-trip — each document reopens as
+/// its own split, in this order, rather than reproducing the original split
+/// arrangement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub working_dir: String,
+    pub documents: Vec<String>,
+}
+
+/// Serialize a selection's ranges into a compact `anchor:head` column,
+/// e.g. `"3:5,10:10"`. One entry per range, in range order.
+fn encode_selection(selection: &Selection) -> String {
+    selection
+        .ranges()
+        .iter()
+        .map(|range| format!("{}:{}", range.anchor, range.head))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Inverse of [`encode_selection`]. Returns `None` if the column is empty or
+/// malformed, so callers can fall back to a single point selection.
+fn decode_selection(encoded: &str, primary_index: usize) -> Option<Selection> {
+    let ranges: Vec<Range> = encoded
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (anchor, head) = pair.split_once(':')?;
+            Some(Range::new(anchor.parse().ok()?, head.parse().ok()?))
+        })
+        .collect::<Option<_>>()?;
+
+    if ranges.is_empty() {
+        return None;
+    }
+
+    let primary_index = primary_index.min(ranges.len() - 1);
+    Some(Selection::new(ranges.into(), primary_index))
 }
 
 impl FileInfoDb {
     pub fn new(enabled: bool) -> Self {
         if !enabled {
             info!("FileInfoDb::new - Feature disabled");
-            return Self { conn: None, enabled: false };
+            return Self { conn: None, enabled: false, history_limit: DEFAULT_HISTORY_LIMIT };
         }
 
         info!("FileInfoDb::new - Feature enabled, initializing database");
@@ -33,7 +104,13 @@ impl FileInfoDb {
             info!("FileInfoDb::new - Failed to establish database connection");
         }
 
-        Self { conn, enabled }
+        Self { conn, enabled, history_limit: DEFAULT_HISTORY_LIMIT }
+    }
+
+    /// Override the number of entries kept in the position history ring
+    /// buffer (defaults to [`DEFAULT_HISTORY_LIMIT`]).
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
     }
 
     fn get_db_path() -> PathBuf {
@@ -55,6 +132,47 @@ impl FileInfoDb {
                 filepath TEXT PRIMARY KEY,
                 line INTEGER NOT NULL,
                 column INTEGER NOT NULL,
+                selection TEXT NOT NULL DEFAULT '',
+                primary_index INTEGER NOT NULL DEFAULT 0,
+                scroll_offset INTEGER NOT NULL DEFAULT 0,
+                last_modified INTEGER NOT NULL
+            )",
+            [],
+        ).ok()?;
+
+        // Databases created before the selection/scroll columns existed
+        // won't have them; add them in place rather than forcing a reset.
+        let _ = conn.execute("ALTER TABLE fileinfo ADD COLUMN selection TEXT NOT NULL DEFAULT ''", []);
+        let _ = conn.execute("ALTER TABLE fileinfo ADD COLUMN primary_index INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE fileinfo ADD COLUMN scroll_offset INTEGER NOT NULL DEFAULT 0", []);
+
+        // Ring-buffered cross-session edit-location history: unlike
+        // `fileinfo`, which keeps one current row per file, this keeps the
+        // last `history_limit` visited positions across every file.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS position_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                filepath TEXT NOT NULL,
+                line INTEGER NOT NULL,
+                column INTEGER NOT NULL,
+                snippet TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        ).ok()?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS position_history_timestamp ON position_history (timestamp)",
+            [],
+        ).ok()?;
+
+        // Session/workspace snapshots: which documents were open, keyed by a
+        // name so ":session-save"/":session-load" can address them and
+        // auto-restore-on-startup can default to the working directory.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                name TEXT PRIMARY KEY,
+                working_dir TEXT NOT NULL,
+                documents TEXT NOT NULL,
                 last_modified INTEGER NOT NULL
             )",
             [],
@@ -63,7 +181,66 @@ impl FileInfoDb {
         Some(conn)
     }
 
-    pub fn save_position(&mut self, path: &Path, selection: &Selection, text: &helix_core::Rope) -> Result<()> {
+    /// The session name used by `:session-save`/`:session-load` when the
+    /// user doesn't give one explicitly: the working directory itself.
+    pub fn session_name_for_cwd(cwd: &Path) -> String {
+        cwd.to_string_lossy().into_owned()
+    }
+
+    pub fn save_session(&self, name: &str, snapshot: &SessionSnapshot) -> Result<()> {
+        if !self.enabled {
+            debug!("FileInfoDb::save_session - not enabled");
+            return Ok(());
+        }
+        let Some(conn) = &self.conn else {
+            return Ok(());
+        };
+
+        let documents_json = serde_json::to_string(&snapshot.documents)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO sessions (name, working_dir, documents, last_modified)
+             VALUES (?1, ?2, ?3, ?4)",
+            (name, &snapshot.working_dir, &documents_json, timestamp),
+        )?;
+        info!("FileInfoDb::save_session - Saved session '{}'", name);
+
+        Ok(())
+    }
+
+    pub fn load_session(&self, name: &str) -> Option<SessionSnapshot> {
+        if !self.enabled {
+            debug!("FileInfoDb::load_session - not enabled");
+            return None;
+        }
+        let conn = self.conn.as_ref()?;
+
+        let mut stmt = conn
+            .prepare("SELECT working_dir, documents FROM sessions WHERE name = ?1")
+            .ok()?;
+
+        let (working_dir, documents_json) = stmt
+            .query_row([name], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .ok()?;
+
+        let documents = serde_json::from_str(&documents_json).ok()?;
+        info!("FileInfoDb::load_session - Loaded session '{}'", name);
+
+        Some(SessionSnapshot { working_dir, documents })
+    }
+
+    pub fn save_position(
+        &mut self,
+        path: &Path,
+        selection: &Selection,
+        text: &helix_core::Rope,
+        scroll_offset: usize,
+    ) -> Result<()> {
         if !self.enabled || self.conn.is_none() {
             debug!("FileInfoDb::save_position - not enabled or no connection");
             return Ok(());
@@ -79,8 +256,11 @@ impl FileInfoDb {
         let position = helix_core::coords_at_pos(text_slice, cursor);
         let (line, column) = (position.row, position.col);
 
-        info!("FileInfoDb::save_position - Saving position for {}: line={}, column={}",
-              filepath, line, column);
+        let selection_text = encode_selection(selection);
+        let primary_index = selection.primary_index();
+
+        info!("FileInfoDb::save_position - Saving position for {}: line={}, column={}, ranges={}",
+              filepath, line, column, selection.len());
 
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -89,16 +269,104 @@ impl FileInfoDb {
 
         if let Some(conn) = &self.conn {
             conn.execute(
-                "INSERT OR REPLACE INTO fileinfo (filepath, line, column, last_modified)
-                 VALUES (?1, ?2, ?3, ?4)",
-                (&filepath, line as i64, column as i64, timestamp),
+                "INSERT OR REPLACE INTO fileinfo (filepath, line, column, selection, primary_index, scroll_offset, last_modified)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                (
+                    &filepath,
+                    line as i64,
+                    column as i64,
+                    &selection_text,
+                    primary_index as i64,
+                    scroll_offset as i64,
+                    timestamp,
+                ),
             )?;
             debug!("FileInfoDb::save_position - Successfully saved to database");
         }
 
+        let snippet: String = text
+            .line(line)
+            .to_string()
+            .trim_end_matches(['\n', '\r'])
+            .chars()
+            .take(HISTORY_SNIPPET_MAX_CHARS)
+            .collect();
+
+        self.append_history(&filepath, line, column, &snippet, timestamp)?;
+
         Ok(())
     }
 
+    /// Append a row to the `position_history` ring buffer and prune it back
+    /// down to `history_limit` entries, oldest first.
+    fn append_history(
+        &self,
+        filepath: &str,
+        line: usize,
+        column: usize,
+        snippet: &str,
+        timestamp: i64,
+    ) -> Result<()> {
+        let Some(conn) = &self.conn else {
+            return Ok(());
+        };
+
+        conn.execute(
+            "INSERT INTO position_history (filepath, line, column, snippet, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            (filepath, line as i64, column as i64, snippet, timestamp),
+        )?;
+
+        conn.execute(
+            "DELETE FROM position_history WHERE id NOT IN (
+                SELECT id FROM position_history ORDER BY timestamp DESC LIMIT ?1
+             )",
+            (self.history_limit as i64,),
+        )?;
+
+        Ok(())
+    }
+
+    /// List the most recent entries in the position history, newest first.
+    pub fn recent_history(&self, limit: usize) -> Vec<HistoryEntry> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let Some(conn) = &self.conn else {
+            return Vec::new();
+        };
+
+        let mut stmt = match conn.prepare(
+            "SELECT filepath, line, column, snippet, timestamp FROM position_history
+             ORDER BY timestamp DESC LIMIT ?1",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                debug!("FileInfoDb::recent_history - Failed to prepare statement: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map((limit as i64,), |row| {
+            Ok(HistoryEntry {
+                filepath: row.get(0)?,
+                line: row.get::<_, i64>(1)? as usize,
+                column: row.get::<_, i64>(2)? as usize,
+                snippet: row.get(3)?,
+                timestamp: row.get(4)?,
+            })
+        });
+
+        match rows {
+            Ok(rows) => rows.filter_map(Result::ok).collect(),
+            Err(e) => {
+                debug!("FileInfoDb::recent_history - Query failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     pub fn load_position(&self, path: &Path) -> Option<FilePosition> {
         if !self.enabled {
             debug!("FileInfoDb::load_position - not enabled");
@@ -120,7 +388,7 @@ impl FileInfoDb {
         let conn = self.conn.as_ref()?;
 
         let mut stmt = match conn.prepare(
-            "SELECT line, column FROM fileinfo WHERE filepath = ?1"
+            "SELECT line, column, selection, primary_index, scroll_offset FROM fileinfo WHERE filepath = ?1"
         ) {
             Ok(s) => s,
             Err(e) => {
@@ -129,22 +397,63 @@ impl FileInfoDb {
             }
         };
 
-        let position = match stmt.query_row([&filepath], |row| {
-            Ok(FilePosition {
-                line: row.get::<_, i64>(0)? as usize,
-                column: row.get::<_, i64>(1)? as usize,
-            })
+        let row = match stmt.query_row([&filepath], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as usize,
+                row.get::<_, i64>(1)? as usize,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)? as usize,
+                row.get::<_, i64>(4)? as usize,
+            ))
         }) {
-            Ok(pos) => {
-                info!("FileInfoDb::load_position - Loaded position: line={}, column={}", pos.line, pos.column);
-                pos
-            }
+            Ok(row) => row,
             Err(e) => {
                 debug!("FileInfoDb::load_position - No saved position found: {}", e);
                 return None;
             }
         };
 
-        Some(position)
+        let (line, column, selection_text, primary_index, scroll_offset) = row;
+        let selection = decode_selection(&selection_text, primary_index)
+            .unwrap_or_else(|| Selection::point(0));
+
+        info!(
+            "FileInfoDb::load_position - Loaded position: line={}, column={}, ranges={}",
+            line, column, selection.len()
+        );
+
+        Some(FilePosition { line, column, selection, scroll_offset })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_selection_round_trip() {
+        let selection = Selection::new(
+            vec![Range::new(3, 5), Range::new(10, 10), Range::new(0, 2)].into(),
+            1,
+        );
+
+        let encoded = encode_selection(&selection);
+        let decoded = decode_selection(&encoded, selection.primary_index())
+            .expect("round-trip should decode");
+
+        assert_eq!(decoded.ranges(), selection.ranges());
+        assert_eq!(decoded.primary_index(), selection.primary_index());
+    }
+
+    #[test]
+    fn decode_selection_rejects_empty_and_malformed_input() {
+        assert!(decode_selection("", 0).is_none());
+        assert!(decode_selection("not-a-range", 0).is_none());
+    }
+
+    #[test]
+    fn decode_selection_clamps_out_of_range_primary_index() {
+        let decoded = decode_selection("0:1,2:3", 5).expect("should decode");
+        assert_eq!(decoded.primary_index(), 1);
     }
 }
\ No newline at end of file